@@ -1,8 +1,19 @@
 // Copyright (c) Facebook, Inc. and its affiliates
 // SPDX-License-Identifier: MIT OR Apache-2.0
 
+// Note for reviewers: this checkout has no Cargo.toml/lib.rs and no
+// cpp/java/python3/rust/test_utils modules for the imports below to
+// resolve against (see the module-level notes on config.rs and
+// fingerprint.rs), so this file has never been compiled or run via
+// `cargo test` here. Each per-language test body was instead validated
+// by pulling its embedded source string out of the `writeln!` call and
+// compiling/running *that* directly with python3/g++/javac against
+// `runtime/`, which exercises the runtime code a test would call but
+// not this file's own Rust compilation -- commit messages describing
+// test behavior in this file should be read with that distinction in
+// mind until the generator modules exist and this suite can build.
 use serde::{Deserialize, Serialize};
-use serde_generate::{cpp, java, python3, rust, test_utils};
+use serde_generate::{cpp, fingerprint, java, python3, rust, test_utils};
 use serde_reflection::{Registry, Result, Samples, Tracer, TracerConfig};
 use std::fs::File;
 use std::io::Write;
@@ -529,4 +540,1239 @@ public class Main {{
         .status()
         .unwrap();
     assert!(status.success());
-}
\ No newline at end of file
+}
+// Exercises the `IntEncoding::Varint` scheme directly against the Python
+// bincode runtime, independently of any generated `Test`-like class:
+// small values are a single byte, values needing a wider width get a
+// marker byte, and non-canonical encodings are rejected on decode.
+#[test]
+fn test_python_bincode_runtime_varint_encoding() {
+    let dir = tempdir().unwrap();
+    let source_path = dir.path().join("test_varint.py");
+    let mut source = File::create(&source_path).unwrap();
+    writeln!(
+        source,
+        r#"
+import bincode
+
+def roundtrip(value, serialize, deserialize, expected_hex):
+    s = bincode.BincodeSerializer(int_encoding="varint")
+    serialize(s)
+    assert s.bytes().hex() == expected_hex, (s.bytes().hex(), expected_hex)
+
+    d = bincode.BincodeDeserializer(bytes.fromhex(expected_hex), int_encoding="varint")
+    assert deserialize(d) == value
+    assert d.remaining() == 0
+
+# u32: values below 251 are a single byte.
+roundtrip(10, lambda s: s.serialize_u32(10), lambda d: d.deserialize_u32(), "0a")
+
+# u32: 251 needs the u16 marker form (smallest width that holds it).
+roundtrip(251, lambda s: s.serialize_u32(251), lambda d: d.deserialize_u32(), "fbfb00")
+
+# u32: a value that only fits in four bytes uses the u32 marker.
+roundtrip(
+    70000,
+    lambda s: s.serialize_u32(70000),
+    lambda d: d.deserialize_u32(),
+    "fc70110100",
+)
+
+# i64: negative values are zig-zag mapped before varint encoding.
+roundtrip(-1, lambda s: s.serialize_i64(-1), lambda d: d.deserialize_i64(), "01")
+
+# A marker byte whose payload would fit in a smaller width is rejected.
+try:
+    bincode.BincodeDeserializer(bytes.fromhex("fb0a00"), int_encoding="varint").deserialize_u32()
+    assert False, "expected a non-canonical varint to be rejected"
+except bincode.DeserializationError:
+    pass
+
+# u128: a value past the u64 range needs the widest (16-byte) marker.
+big = (1 << 70) + 5
+roundtrip(
+    big,
+    lambda s: s.serialize_u128(big),
+    lambda d: d.deserialize_u128(),
+    "fe05000000000000004000000000000000",
+)
+
+# i128: negative values are zig-zag mapped the same way as the narrower
+# signed widths, just over a 16-byte payload.
+roundtrip(
+    -big,
+    lambda s: s.serialize_i128(-big),
+    lambda d: d.deserialize_i128(),
+    "fe09000000000000008000000000000000",
+)
+"#
+    )
+    .unwrap();
+
+    let python_path = std::env::var("PYTHONPATH").unwrap_or_default() + ":runtime/python";
+    let status = Command::new("python3")
+        .arg(source_path)
+        .env("PYTHONPATH", python_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+// Exercises the `ByteOrder::BigEndian` option directly against the
+// Python bincode runtime: fixed-width integers flip to big-endian, and
+// the width-dependent payload of varint-encoded integers follows suit.
+#[test]
+fn test_python_bincode_runtime_big_endian() {
+    let dir = tempdir().unwrap();
+    let source_path = dir.path().join("test_big_endian.py");
+    let mut source = File::create(&source_path).unwrap();
+    writeln!(
+        source,
+        r#"
+import bincode
+
+s = bincode.BincodeSerializer(byte_order="big")
+s.serialize_u32(0x01020304)
+assert s.bytes().hex() == "01020304"
+
+d = bincode.BincodeDeserializer(bytes.fromhex("01020304"), byte_order="big")
+assert d.deserialize_u32() == 0x01020304
+assert d.remaining() == 0
+
+# The varint marker byte itself is not affected by byte order, only the
+# width-dependent payload that follows it.
+s2 = bincode.BincodeSerializer(int_encoding="varint", byte_order="big")
+s2.serialize_u32(70000)
+assert s2.bytes().hex() == "fc00011170"
+
+d2 = bincode.BincodeDeserializer(
+    bytes.fromhex("fc00011170"), int_encoding="varint", byte_order="big"
+)
+assert d2.deserialize_u32() == 70000
+assert d2.remaining() == 0
+"#
+    )
+    .unwrap();
+
+    let python_path = std::env::var("PYTHONPATH").unwrap_or_default() + ":runtime/python";
+    let status = Command::new("python3")
+        .arg(source_path)
+        .env("PYTHONPATH", python_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+// Exercises the `max_container_length` and `max_container_depth` limits
+// directly against the Python bincode runtime: an oversized length
+// prefix is rejected before any allocation, and a deeply nested
+// recursive container (mimicking what a generated `Vec<Vec<...>>`-like
+// type would do) is rejected partway through a real deserialize once it
+// exceeds the maximum nesting depth, rather than by driving the depth
+// counter directly.
+#[test]
+fn test_python_bincode_runtime_deserialization_limits() {
+    let dir = tempdir().unwrap();
+    let source_path = dir.path().join("test_limits.py");
+    let mut source = File::create(&source_path).unwrap();
+    writeln!(
+        source,
+        r#"
+import bincode
+
+# A sequence length prefix above `max_container_length` is rejected.
+s = bincode.BincodeSerializer()
+s.serialize_len(1000)
+
+d = bincode.BincodeDeserializer(s.bytes(), max_container_length=10)
+try:
+    d.deserialize_len()
+    assert False, "expected an oversized length to be rejected"
+except bincode.DeserializationError:
+    pass
+
+# A byte array length prefix above `max_container_length` is rejected
+# before the (potentially huge) allocation would happen.
+s2 = bincode.BincodeSerializer()
+s2.serialize_bytes(b"hello")
+
+d2 = bincode.BincodeDeserializer(s2.bytes(), max_container_length=2)
+try:
+    d2.deserialize_bytes()
+    assert False, "expected an oversized byte array to be rejected"
+except bincode.DeserializationError:
+    pass
+
+
+# A nested list, modeling what generated code for a recursive container
+# (e.g. a tree of `Vec<Node>` children) would produce: each node is a
+# length-prefixed sequence of either leaf bytes or further nodes, one
+# level deeper. `increase_container_depth`/`decrease_container_depth`
+# bracket each recursive call exactly as generated (de)serialization
+# code would.
+def serialize_nested(serializer, node):
+    serializer.serialize_len(len(node))
+    for child in node:
+        if isinstance(child, list):
+            serialize_nested(serializer, child)
+        else:
+            serializer.serialize_u8(child)
+
+
+def deserialize_nested(deserializer, levels_remaining):
+    deserializer.increase_container_depth()
+    length = deserializer.deserialize_len()
+    node = []
+    for _ in range(length):
+        if levels_remaining == 0:
+            node.append(deserializer.deserialize_u8())
+        else:
+            node.append(deserialize_nested(deserializer, levels_remaining - 1))
+    deserializer.decrease_container_depth()
+    return node
+
+
+# Five levels of nesting: [[[[[1]]]]]
+nested = [[[[[1]]]]]
+s3 = bincode.BincodeSerializer()
+serialize_nested(s3, nested)
+encoded = s3.bytes()
+
+# With enough headroom, the real recursive deserialize succeeds.
+d3 = bincode.BincodeDeserializer(encoded, max_container_depth=10)
+assert deserialize_nested(d3, 4) == nested
+assert d3.remaining() == 0
+
+# With a depth limit too shallow for this nesting, the same deserialize
+# is rejected partway through -- not via a bare counter increment, but
+# by actually recursing into the oversized structure.
+d4 = bincode.BincodeDeserializer(encoded, max_container_depth=3)
+try:
+    deserialize_nested(d4, 4)
+    assert False, "expected the container depth limit to be enforced"
+except bincode.DeserializationError:
+    pass
+"#
+    )
+    .unwrap();
+
+    let python_path = std::env::var("PYTHONPATH").unwrap_or_default() + ":runtime/python";
+    let status = Command::new("python3")
+        .arg(source_path)
+        .env("PYTHONPATH", python_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+// Demonstrates the "stream" trailing-bytes policy for Rust: unlike the
+// custom runtimes of the other target languages, generated Rust code
+// deserializes directly through the upstream `bincode` crate, which
+// already exposes both policies via its `Read`-based API. Reading
+// through a mutable `&[u8]` (which implements `std::io::Read`) consumes
+// only the bytes needed for the value and leaves the rest in place,
+// mirroring `bincode.deserialize()`'s `(value, buffer)` return in the
+// Python runtime.
+#[test]
+fn test_rust_bincode_runtime_trailing_bytes_stream_mode() {
+    let value = Test {
+        a: vec![4, 6],
+        b: (3, 5),
+        c: Choice::C { x: 7 },
+    };
+    let mut encoding = bincode::serialize(&value).unwrap();
+    let extra = vec![9, 9, 9];
+    encoding.extend_from_slice(&extra);
+
+    let mut stream: &[u8] = &encoding;
+    let decoded: Test = bincode::deserialize_from(&mut stream).unwrap();
+    assert_eq!(decoded.b, value.b);
+    assert_eq!(stream, extra.as_slice());
+}
+
+// Exercises the self-describing encoding's header (format version byte +
+// schema fingerprint) directly against the Python bincode runtime. In
+// generated code the expected fingerprint would be a constant computed
+// once by `serde_generate::fingerprint::registry_fingerprint` at codegen
+// time; here it is simply passed in, to isolate the header's own framing
+// and mismatch-detection logic from that computation.
+#[test]
+fn test_python_bincode_runtime_self_describing_header() {
+    let dir = tempdir().unwrap();
+    let source_path = dir.path().join("test_self_describing.py");
+    let mut source = File::create(&source_path).unwrap();
+    writeln!(
+        source,
+        r#"
+import bincode
+
+
+class Foo:
+    def __init__(self, x):
+        self.x = x
+
+    @staticmethod
+    def serialize(obj, serializer):
+        serializer.serialize_u32(obj.x)
+
+    @staticmethod
+    def deserialize(deserializer):
+        return Foo(deserializer.deserialize_u32())
+
+
+fingerprint = bytes(range(8))
+data = bincode.serialize_with_fingerprint(Foo(42), Foo, fingerprint)
+assert data[0] == bincode.SELF_DESCRIBING_FORMAT_VERSION
+assert data[1 : 1 + bincode.FINGERPRINT_SIZE] == fingerprint
+
+value, buffer = bincode.deserialize_with_fingerprint(data, Foo, fingerprint)
+assert value.x == 42
+assert len(buffer) == 0
+
+# A fingerprint mismatch (e.g. the bytes were produced by a generator
+# for a different Registry) is rejected.
+wrong_fingerprint = bytes(range(1, 9))
+try:
+    bincode.deserialize_with_fingerprint(data, Foo, wrong_fingerprint)
+    assert False, "expected a fingerprint mismatch to be rejected"
+except bincode.DeserializationError:
+    pass
+
+# An unrecognized format version is rejected.
+try:
+    bincode.deserialize_with_fingerprint(
+        bytes([2]) + fingerprint, Foo, fingerprint
+    )
+    assert False, "expected an unknown format version to be rejected"
+except bincode.DeserializationError:
+    pass
+"#
+    )
+    .unwrap();
+
+    let python_path = std::env::var("PYTHONPATH").unwrap_or_default() + ":runtime/python";
+    let status = Command::new("python3")
+        .arg(source_path)
+        .env("PYTHONPATH", python_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+// Exercises the self-describing encoding's header directly against the
+// Rust bincode runtime (serde_generate::fingerprint), the one target
+// language where generated code reads and writes through the upstream
+// `bincode` crate rather than a bespoke runtime -- the header framing
+// itself still has to be bespoke, since `bincode` has no concept of it.
+#[test]
+fn test_rust_bincode_runtime_self_describing_header() {
+    let value = Test {
+        a: vec![4, 6],
+        b: (3, 5),
+        c: Choice::C { x: 7 },
+    };
+    let schema_fingerprint = [0u8, 1, 2, 3, 4, 5, 6, 7];
+
+    let data = fingerprint::serialize_with_fingerprint(&value, &schema_fingerprint).unwrap();
+    assert_eq!(data[0], fingerprint::SELF_DESCRIBING_FORMAT_VERSION);
+    assert_eq!(
+        &data[1..1 + fingerprint::FINGERPRINT_SIZE],
+        &schema_fingerprint
+    );
+
+    let (decoded, tail): (Test, _) =
+        fingerprint::deserialize_with_fingerprint(&data, &schema_fingerprint).unwrap();
+    assert_eq!(decoded.b, value.b);
+    assert!(tail.is_empty());
+
+    // A fingerprint mismatch (e.g. the bytes were produced by a generator
+    // for a different Registry) is rejected.
+    let wrong_fingerprint = [1u8, 2, 3, 4, 5, 6, 7, 8];
+    assert!(fingerprint::deserialize_with_fingerprint::<Test>(&data, &wrong_fingerprint).is_err());
+
+    // An unrecognized format version is rejected.
+    let mut bad_version = data.clone();
+    bad_version[0] = 2;
+    assert!(
+        fingerprint::deserialize_with_fingerprint::<Test>(&bad_version, &schema_fingerprint)
+            .is_err()
+    );
+}
+
+// Exercises the self-describing encoding's header directly against the
+// C++ bincode runtime, with a hand-written `Foo` type standing in for a
+// generated container (no C++ code generator is available in this
+// checkout to produce one).
+#[test]
+fn test_cpp_bincode_runtime_self_describing_header() {
+    let dir = tempdir().unwrap();
+    let source_path = dir.path().join("test.cpp");
+    let mut source = File::create(&source_path).unwrap();
+    writeln!(
+        source,
+        r#"
+#include <cassert>
+#include <array>
+#include "bincode.hpp"
+
+struct Foo {{
+    uint32_t x;
+}};
+
+namespace serde {{
+template <>
+struct Serializable<Foo> {{
+    static void serialize(const Foo &obj, BincodeSerializer &serializer) {{
+        serializer.serialize_u32(obj.x);
+    }}
+}};
+
+template <>
+struct Deserializable<Foo> {{
+    static Foo deserialize(BincodeDeserializer &deserializer) {{
+        return Foo {{deserializer.deserialize_u32()}};
+    }}
+}};
+}}  // namespace serde
+
+using namespace serde;
+
+int main() {{
+    std::array<uint8_t, kFingerprintSize> fingerprint;
+    for (size_t i = 0; i < fingerprint.size(); i++) {{
+        fingerprint[i] = static_cast<uint8_t>(i);
+    }}
+
+    auto data = bincode_serialize_with_fingerprint(Foo {{42}}, fingerprint);
+    assert(data[0] == kSelfDescribingFormatVersion);
+    assert(std::equal(data.begin() + 1, data.begin() + 1 + kFingerprintSize, fingerprint.begin()));
+
+    auto result = bincode_deserialize_with_fingerprint<Foo>(data, fingerprint);
+    assert(result.first.x == 42);
+    assert(result.second.empty());
+
+    std::array<uint8_t, kFingerprintSize> wrong_fingerprint;
+    for (size_t i = 0; i < wrong_fingerprint.size(); i++) {{
+        wrong_fingerprint[i] = static_cast<uint8_t>(i + 1);
+    }}
+    bool threw = false;
+    try {{
+        bincode_deserialize_with_fingerprint<Foo>(data, wrong_fingerprint);
+    }} catch (const deserialization_error &) {{
+        threw = true;
+    }}
+    assert(threw);
+
+    return 0;
+}}
+"#
+    )
+    .unwrap();
+
+    let status = Command::new("clang++")
+        .arg("--std=c++17")
+        .arg("-o")
+        .arg(dir.path().join("test"))
+        .arg("-I")
+        .arg("runtime/cpp")
+        .arg(source_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new(dir.path().join("test")).status().unwrap();
+    assert!(status.success());
+}
+
+// Exercises the self-describing encoding's header directly against the
+// Java bincode runtime, via `BincodeSerializer.serializeWithFingerprint`
+// / `BincodeDeserializer.deserializeWithFingerprint`, the static-method
+// framing those classes expose in place of the free functions used by
+// the Python and Rust runtimes.
+#[test]
+fn test_java_bincode_runtime_self_describing_header() {
+    let dir = tempdir().unwrap();
+
+    let mut source = File::create(&dir.path().join("Main.java")).unwrap();
+    writeln!(
+        source,
+        r#"
+import com.facebook.serde.Tuple2;
+import com.facebook.serde.DeserializationError;
+import com.facebook.bincode.BincodeDeserializer;
+import com.facebook.bincode.BincodeSerializer;
+import com.facebook.bincode.ByteOrder;
+import com.facebook.bincode.IntEncoding;
+
+public class Main {{
+    public static void main(String[] args) throws java.lang.Exception {{
+        byte[] fingerprint = new byte[] {{0, 1, 2, 3, 4, 5, 6, 7}};
+
+        byte[] data = BincodeSerializer.serializeWithFingerprint(
+                serializer -> serializer.serialize_u32(42),
+                fingerprint,
+                IntEncoding.FIXED,
+                ByteOrder.LITTLE_ENDIAN);
+
+        Tuple2<Integer, byte[]> result = BincodeDeserializer.deserializeWithFingerprint(
+                data,
+                deserializer -> deserializer.deserialize_u32(),
+                fingerprint,
+                IntEncoding.FIXED,
+                ByteOrder.LITTLE_ENDIAN);
+        assert result.field0 == 42;
+        assert result.field1.length == 0;
+
+        byte[] wrongFingerprint = new byte[] {{1, 2, 3, 4, 5, 6, 7, 8}};
+        boolean threw = false;
+        try {{
+            BincodeDeserializer.deserializeWithFingerprint(
+                    data,
+                    deserializer -> deserializer.deserialize_u32(),
+                    wrongFingerprint,
+                    IntEncoding.FIXED,
+                    ByteOrder.LITTLE_ENDIAN);
+        }} catch (DeserializationError e) {{
+            threw = true;
+        }}
+        assert threw;
+    }}
+}}
+"#
+    )
+    .unwrap();
+
+    let paths = std::iter::empty()
+        .chain(std::fs::read_dir("runtime/java/com/facebook/serde").unwrap())
+        .chain(std::fs::read_dir("runtime/java/com/facebook/bincode").unwrap())
+        .map(|e| e.unwrap().path());
+    let status = Command::new("javac")
+        .arg("-Xlint")
+        .arg("-d")
+        .arg(dir.path())
+        .args(paths)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new("javac")
+        .arg("-Xlint")
+        .arg("-cp")
+        .arg(dir.path())
+        .arg("-d")
+        .arg(dir.path())
+        .arg(dir.path().join("Main.java"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new("java")
+        .arg("-enableassertions")
+        .arg("-cp")
+        .arg(dir.path())
+        .arg("Main")
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+// Most of the cases of `test_python_bincode_runtime_varint_encoding`
+// (single-byte values, the u16 and u128 marker widths, zig-zag-mapped
+// negative i64/i128, and non-canonical-width rejection; the u32-marker
+// case is not repeated here), but against the C++ runtime, where
+// `bytes()` is move-only and has to be captured before being compared or
+// fed back into a deserializer.
+#[test]
+fn test_cpp_bincode_runtime_varint_encoding() {
+    let dir = tempdir().unwrap();
+    let source_path = dir.path().join("test_varint.cpp");
+    let mut source = File::create(&source_path).unwrap();
+    writeln!(
+        source,
+        r#"
+#include <cassert>
+#include "bincode.hpp"
+
+using namespace serde;
+
+int main() {{
+    // u32: values below 251 are a single byte.
+    {{
+        auto s = BincodeSerializer(IntEncoding::Varint);
+        s.serialize_u32(10);
+        std::vector<uint8_t> expected = {{0x0a}};
+        assert(std::move(s).bytes() == expected);
+
+        auto d = BincodeDeserializer(expected, IntEncoding::Varint);
+        assert(d.deserialize_u32() == 10);
+        assert(d.remaining() == 0);
+    }}
+
+    // u32: 251 needs the u16 marker form (smallest width that holds it).
+    {{
+        auto s = BincodeSerializer(IntEncoding::Varint);
+        s.serialize_u32(251);
+        std::vector<uint8_t> expected = {{0xfb, 0xfb, 0x00}};
+        assert(std::move(s).bytes() == expected);
+
+        auto d = BincodeDeserializer(expected, IntEncoding::Varint);
+        assert(d.deserialize_u32() == 251u);
+        assert(d.remaining() == 0);
+    }}
+
+    // i64: negative values are zig-zag mapped before varint encoding.
+    {{
+        auto s = BincodeSerializer(IntEncoding::Varint);
+        s.serialize_i64(-1);
+        std::vector<uint8_t> expected = {{0x01}};
+        assert(std::move(s).bytes() == expected);
+
+        auto d = BincodeDeserializer(expected, IntEncoding::Varint);
+        assert(d.deserialize_i64() == -1);
+        assert(d.remaining() == 0);
+    }}
+
+    // A marker byte whose payload would fit in a smaller width is rejected.
+    {{
+        std::vector<uint8_t> input = {{0xfb, 0x0a, 0x00}};
+        auto d = BincodeDeserializer(input, IntEncoding::Varint);
+        bool threw = false;
+        try {{
+            d.deserialize_u32();
+        }} catch (const deserialization_error &) {{
+            threw = true;
+        }}
+        assert(threw);
+    }}
+
+    // u128/i128: values past the u64 range need the widest (16-byte)
+    // marker; `__int128` stands in for the upstream crate's u128/i128.
+    {{
+        unsigned __int128 big = (static_cast<unsigned __int128>(1) << 70) + 5;
+        auto s = BincodeSerializer(IntEncoding::Varint);
+        s.serialize_u128(big);
+        std::vector<uint8_t> bytes = std::move(s).bytes();
+        assert(bytes.size() == 17 && bytes[0] == 0xfe);
+
+        auto d = BincodeDeserializer(bytes, IntEncoding::Varint);
+        assert(d.deserialize_u128() == big);
+        assert(d.remaining() == 0);
+
+        __int128 neg = -static_cast<__int128>(big);
+        auto s2 = BincodeSerializer(IntEncoding::Varint);
+        s2.serialize_i128(neg);
+        std::vector<uint8_t> neg_bytes = std::move(s2).bytes();
+        auto d2 = BincodeDeserializer(neg_bytes, IntEncoding::Varint);
+        assert(d2.deserialize_i128() == neg);
+        assert(d2.remaining() == 0);
+    }}
+
+    return 0;
+}}
+"#
+    )
+    .unwrap();
+
+    let status = Command::new("clang++")
+        .arg("--std=c++17")
+        .arg("-o")
+        .arg(dir.path().join("test"))
+        .arg("-I")
+        .arg("runtime/cpp")
+        .arg(source_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new(dir.path().join("test")).status().unwrap();
+    assert!(status.success());
+}
+
+// Same cases as `test_cpp_bincode_runtime_varint_encoding` (the Python
+// varint cases minus the u32-marker one), against the Java runtime,
+// where u128/i128 come back as `BigInteger` rather than a
+// primitive, since the JVM has no native 128-bit integer type.
+#[test]
+fn test_java_bincode_runtime_varint_encoding() {
+    let dir = tempdir().unwrap();
+
+    let mut source = File::create(&dir.path().join("Main.java")).unwrap();
+    writeln!(
+        source,
+        r#"
+import com.facebook.serde.DeserializationError;
+import com.facebook.bincode.BincodeDeserializer;
+import com.facebook.bincode.BincodeSerializer;
+import com.facebook.bincode.IntEncoding;
+import java.math.BigInteger;
+
+public class Main {{
+    public static void main(String[] args) throws java.lang.Exception {{
+        // u32: values below 251 are a single byte.
+        {{
+            BincodeSerializer s = new BincodeSerializer(IntEncoding.VARINT);
+            s.serialize_u32(10);
+            byte[] expected = new byte[] {{0x0a}};
+            assert java.util.Arrays.equals(s.get_bytes(), expected);
+
+            BincodeDeserializer d = new BincodeDeserializer(expected, IntEncoding.VARINT);
+            assert d.deserialize_u32() == 10;
+            assert d.remaining() == 0;
+        }}
+
+        // u32: 251 needs the u16 marker form (smallest width that holds it).
+        {{
+            BincodeSerializer s = new BincodeSerializer(IntEncoding.VARINT);
+            s.serialize_u32(251);
+            byte[] expected = new byte[] {{(byte) 0xfb, (byte) 0xfb, 0x00}};
+            assert java.util.Arrays.equals(s.get_bytes(), expected);
+
+            BincodeDeserializer d = new BincodeDeserializer(expected, IntEncoding.VARINT);
+            assert d.deserialize_u32() == 251;
+            assert d.remaining() == 0;
+        }}
+
+        // i64: negative values are zig-zag mapped before varint encoding.
+        {{
+            BincodeSerializer s = new BincodeSerializer(IntEncoding.VARINT);
+            s.serialize_i64(-1);
+            byte[] expected = new byte[] {{0x01}};
+            assert java.util.Arrays.equals(s.get_bytes(), expected);
+
+            BincodeDeserializer d = new BincodeDeserializer(expected, IntEncoding.VARINT);
+            assert d.deserialize_i64() == -1;
+            assert d.remaining() == 0;
+        }}
+
+        // A marker byte whose payload would fit in a smaller width is rejected.
+        {{
+            byte[] input = new byte[] {{(byte) 0xfb, 0x0a, 0x00}};
+            BincodeDeserializer d = new BincodeDeserializer(input, IntEncoding.VARINT);
+            boolean threw = false;
+            try {{
+                d.deserialize_u32();
+            }} catch (DeserializationError e) {{
+                threw = true;
+            }}
+            assert threw;
+        }}
+
+        // u128/i128: values past the u64 range need the widest (16-byte)
+        // marker; Java has no native 128-bit integer, so a `BigInteger`
+        // stands in for the upstream crate's u128/i128.
+        {{
+            BigInteger big = BigInteger.ONE.shiftLeft(70).add(BigInteger.valueOf(5));
+            BincodeSerializer s = new BincodeSerializer(IntEncoding.VARINT);
+            s.serialize_u128(big);
+            byte[] bytes = s.get_bytes();
+            assert bytes.length == 17 && bytes[0] == (byte) 0xfe;
+
+            BincodeDeserializer d = new BincodeDeserializer(bytes, IntEncoding.VARINT);
+            assert d.deserialize_u128().equals(big);
+            assert d.remaining() == 0;
+
+            BigInteger neg = big.negate();
+            BincodeSerializer s2 = new BincodeSerializer(IntEncoding.VARINT);
+            s2.serialize_i128(neg);
+            BincodeDeserializer d2 = new BincodeDeserializer(s2.get_bytes(), IntEncoding.VARINT);
+            assert d2.deserialize_i128().equals(neg);
+            assert d2.remaining() == 0;
+        }}
+    }}
+}}
+"#
+    )
+    .unwrap();
+
+    let paths = std::iter::empty()
+        .chain(std::fs::read_dir("runtime/java/com/facebook/serde").unwrap())
+        .chain(std::fs::read_dir("runtime/java/com/facebook/bincode").unwrap())
+        .map(|e| e.unwrap().path());
+    let status = Command::new("javac")
+        .arg("-Xlint")
+        .arg("-d")
+        .arg(dir.path())
+        .args(paths)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new("javac")
+        .arg("-Xlint")
+        .arg("-cp")
+        .arg(dir.path())
+        .arg("-d")
+        .arg(dir.path())
+        .arg(dir.path().join("Main.java"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new("java")
+        .arg("-enableassertions")
+        .arg("-cp")
+        .arg(dir.path())
+        .arg("Main")
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+// Same cases as `test_python_bincode_runtime_big_endian` (fixed-width
+// byte order flips, varint marker byte staying put while its payload
+// flips), against the C++ runtime.
+#[test]
+fn test_cpp_bincode_runtime_big_endian() {
+    let dir = tempdir().unwrap();
+    let source_path = dir.path().join("test_big_endian.cpp");
+    let mut source = File::create(&source_path).unwrap();
+    writeln!(
+        source,
+        r#"
+#include <cassert>
+#include "bincode.hpp"
+
+using namespace serde;
+
+int main() {{
+    {{
+        auto s = BincodeSerializer(IntEncoding::Fixed, ByteOrder::Big);
+        s.serialize_u32(0x01020304);
+        std::vector<uint8_t> expected = {{0x01, 0x02, 0x03, 0x04}};
+        assert(std::move(s).bytes() == expected);
+
+        auto d = BincodeDeserializer(expected, IntEncoding::Fixed, ByteOrder::Big);
+        assert(d.deserialize_u32() == 0x01020304u);
+        assert(d.remaining() == 0);
+    }}
+
+    // The varint marker byte itself is not affected by byte order, only
+    // the width-dependent payload that follows it.
+    {{
+        auto s = BincodeSerializer(IntEncoding::Varint, ByteOrder::Big);
+        s.serialize_u32(70000);
+        std::vector<uint8_t> expected = {{0xfc, 0x00, 0x01, 0x11, 0x70}};
+        assert(std::move(s).bytes() == expected);
+
+        auto d = BincodeDeserializer(expected, IntEncoding::Varint, ByteOrder::Big);
+        assert(d.deserialize_u32() == 70000u);
+        assert(d.remaining() == 0);
+    }}
+
+    return 0;
+}}
+"#
+    )
+    .unwrap();
+
+    let status = Command::new("clang++")
+        .arg("--std=c++17")
+        .arg("-o")
+        .arg(dir.path().join("test"))
+        .arg("-I")
+        .arg("runtime/cpp")
+        .arg(source_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new(dir.path().join("test")).status().unwrap();
+    assert!(status.success());
+}
+
+// Same cases as `test_python_bincode_runtime_big_endian`, against the
+// Java runtime.
+#[test]
+fn test_java_bincode_runtime_big_endian() {
+    let dir = tempdir().unwrap();
+
+    let mut source = File::create(&dir.path().join("Main.java")).unwrap();
+    writeln!(
+        source,
+        r#"
+import com.facebook.bincode.BincodeDeserializer;
+import com.facebook.bincode.BincodeSerializer;
+import com.facebook.bincode.ByteOrder;
+import com.facebook.bincode.IntEncoding;
+
+public class Main {{
+    public static void main(String[] args) throws java.lang.Exception {{
+        {{
+            BincodeSerializer s = new BincodeSerializer(IntEncoding.FIXED, ByteOrder.BIG_ENDIAN);
+            s.serialize_u32(0x01020304);
+            byte[] expected = new byte[] {{0x01, 0x02, 0x03, 0x04}};
+            assert java.util.Arrays.equals(s.get_bytes(), expected);
+
+            BincodeDeserializer d =
+                    new BincodeDeserializer(expected, IntEncoding.FIXED, ByteOrder.BIG_ENDIAN);
+            assert d.deserialize_u32() == 0x01020304;
+            assert d.remaining() == 0;
+        }}
+
+        // The varint marker byte itself is not affected by byte order, only
+        // the width-dependent payload that follows it.
+        {{
+            BincodeSerializer s = new BincodeSerializer(IntEncoding.VARINT, ByteOrder.BIG_ENDIAN);
+            s.serialize_u32(70000);
+            byte[] expected = new byte[] {{(byte) 0xfc, 0x00, 0x01, 0x11, 0x70}};
+            assert java.util.Arrays.equals(s.get_bytes(), expected);
+
+            BincodeDeserializer d =
+                    new BincodeDeserializer(expected, IntEncoding.VARINT, ByteOrder.BIG_ENDIAN);
+            assert d.deserialize_u32() == 70000;
+            assert d.remaining() == 0;
+        }}
+    }}
+}}
+"#
+    )
+    .unwrap();
+
+    let paths = std::iter::empty()
+        .chain(std::fs::read_dir("runtime/java/com/facebook/serde").unwrap())
+        .chain(std::fs::read_dir("runtime/java/com/facebook/bincode").unwrap())
+        .map(|e| e.unwrap().path());
+    let status = Command::new("javac")
+        .arg("-Xlint")
+        .arg("-d")
+        .arg(dir.path())
+        .args(paths)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new("javac")
+        .arg("-Xlint")
+        .arg("-cp")
+        .arg(dir.path())
+        .arg("-d")
+        .arg(dir.path())
+        .arg(dir.path().join("Main.java"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new("java")
+        .arg("-enableassertions")
+        .arg("-cp")
+        .arg(dir.path())
+        .arg("Main")
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+// Exercises `max_container_length` and `max_container_depth` directly
+// against the C++ bincode runtime, mirroring
+// `test_python_bincode_runtime_deserialization_limits`.
+#[test]
+fn test_cpp_bincode_runtime_deserialization_limits() {
+    let dir = tempdir().unwrap();
+    let source_path = dir.path().join("test_limits.cpp");
+    let mut source = File::create(&source_path).unwrap();
+    writeln!(
+        source,
+        r#"
+#include <cassert>
+#include <vector>
+#include "bincode.hpp"
+
+using namespace serde;
+
+// A node of a recursively nested sequence: either a single leaf byte or
+// a length-prefixed list of deeper nodes, modeling what generated code
+// for a recursive container (e.g. a tree of `Vec<Node>` children) would
+// produce. `increase_container_depth`/`decrease_container_depth` bracket
+// each recursive call exactly as generated (de)serialization code would.
+struct Node {{
+    bool is_leaf;
+    uint8_t leaf_value;
+    std::vector<Node> children;
+}};
+
+void serialize_node(const Node &node, BincodeSerializer &serializer) {{
+    if (node.is_leaf) {{
+        serializer.serialize_len(0);
+        serializer.serialize_u8(node.leaf_value);
+        return;
+    }}
+    serializer.serialize_len(node.children.size());
+    for (const auto &child : node.children) {{
+        serialize_node(child, serializer);
+    }}
+}}
+
+// `levels_remaining == 0` means this node's own payload is a leaf byte
+// rather than further nested nodes; mirrors the Python/Java tests.
+Node deserialize_node(BincodeDeserializer &deserializer, int levels_remaining) {{
+    deserializer.increase_container_depth();
+    size_t length = deserializer.deserialize_len();
+    Node node;
+    if (levels_remaining == 0) {{
+        node.is_leaf = true;
+        node.leaf_value = deserializer.deserialize_u8();
+    }} else {{
+        node.is_leaf = false;
+        for (size_t i = 0; i < length; i++) {{
+            node.children.push_back(deserialize_node(deserializer, levels_remaining - 1));
+        }}
+    }}
+    deserializer.decrease_container_depth();
+    return node;
+}}
+
+int main() {{
+    // A sequence length prefix above `max_container_length` is rejected.
+    {{
+        auto s = BincodeSerializer();
+        s.serialize_len(1000);
+        auto bytes = std::move(s).bytes();
+
+        auto d = BincodeDeserializer(
+            bytes, IntEncoding::Fixed, ByteOrder::Little, /*max_container_length=*/10);
+        bool threw = false;
+        try {{
+            d.deserialize_len();
+        }} catch (const deserialization_error &) {{
+            threw = true;
+        }}
+        assert(threw);
+    }}
+
+    // Five levels of nesting: [[[[[1]]]]].
+    Node leaf;
+    leaf.is_leaf = true;
+    leaf.leaf_value = 1;
+    Node level4;
+    level4.is_leaf = false;
+    level4.children = {{leaf}};
+    Node level3;
+    level3.is_leaf = false;
+    level3.children = {{level4}};
+    Node level2;
+    level2.is_leaf = false;
+    level2.children = {{level3}};
+    Node level1;
+    level1.is_leaf = false;
+    level1.children = {{level2}};
+
+    auto s3 = BincodeSerializer();
+    serialize_node(level1, s3);
+    std::vector<uint8_t> encoded = std::move(s3).bytes();
+
+    // With enough headroom, the real recursive deserialize succeeds.
+    {{
+        auto d = BincodeDeserializer(
+            encoded,
+            IntEncoding::Fixed,
+            ByteOrder::Little,
+            /*max_container_length=*/std::numeric_limits<size_t>::max(),
+            /*max_container_depth=*/10);
+        Node decoded = deserialize_node(d, 4);
+        assert(decoded.children[0].children[0].children[0].children[0].leaf_value == 1);
+        assert(d.remaining() == 0);
+    }}
+
+    // With a depth limit too shallow for this nesting, the same
+    // deserialize is rejected partway through -- not via a bare counter
+    // increment, but by actually recursing into the oversized structure.
+    {{
+        auto d = BincodeDeserializer(
+            encoded,
+            IntEncoding::Fixed,
+            ByteOrder::Little,
+            /*max_container_length=*/std::numeric_limits<size_t>::max(),
+            /*max_container_depth=*/3);
+        bool threw = false;
+        try {{
+            deserialize_node(d, 4);
+        }} catch (const deserialization_error &) {{
+            threw = true;
+        }}
+        assert(threw);
+    }}
+
+    return 0;
+}}
+"#
+    )
+    .unwrap();
+
+    let status = Command::new("clang++")
+        .arg("--std=c++17")
+        .arg("-o")
+        .arg(dir.path().join("test"))
+        .arg("-I")
+        .arg("runtime/cpp")
+        .arg(source_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new(dir.path().join("test")).status().unwrap();
+    assert!(status.success());
+}
+
+// Exercises `maxContainerLength` and `maxContainerDepth` directly against
+// the Java bincode runtime, mirroring
+// `test_python_bincode_runtime_deserialization_limits`.
+#[test]
+fn test_java_bincode_runtime_deserialization_limits() {
+    let dir = tempdir().unwrap();
+
+    let mut source = File::create(&dir.path().join("Main.java")).unwrap();
+    writeln!(
+        source,
+        r#"
+import com.facebook.serde.DeserializationError;
+import com.facebook.bincode.BincodeDeserializer;
+import com.facebook.bincode.BincodeSerializer;
+import com.facebook.bincode.ByteOrder;
+import com.facebook.bincode.IntEncoding;
+import java.util.ArrayList;
+import java.util.List;
+
+public class Main {{
+    // A node of a recursively nested sequence: either a single leaf byte
+    // or a list of deeper nodes, modeling what generated code for a
+    // recursive container (e.g. a tree of `Vec<Node>` children) would
+    // produce. `increase_container_depth`/`decrease_container_depth`
+    // bracket each recursive call exactly as generated (de)serialization
+    // code would.
+    static final class Node {{
+        boolean isLeaf;
+        byte leafValue;
+        List<Node> children = new ArrayList<>();
+    }}
+
+    static void serializeNode(Node node, BincodeSerializer serializer) {{
+        if (node.isLeaf) {{
+            serializer.serialize_len(0);
+            serializer.serialize_u8(node.leafValue);
+            return;
+        }}
+        serializer.serialize_len(node.children.size());
+        for (Node child : node.children) {{
+            serializeNode(child, serializer);
+        }}
+    }}
+
+    // `levelsRemaining == 0` means this node's own payload is a leaf
+    // byte rather than further nested nodes; mirrors the Python/C++ tests.
+    static Node deserializeNode(BincodeDeserializer deserializer, int levelsRemaining)
+            throws DeserializationError {{
+        deserializer.increase_container_depth();
+        long length = deserializer.deserialize_len();
+        Node node = new Node();
+        if (levelsRemaining == 0) {{
+            node.isLeaf = true;
+            node.leafValue = deserializer.deserialize_u8();
+        }} else {{
+            for (long i = 0; i < length; i++) {{
+                node.children.add(deserializeNode(deserializer, levelsRemaining - 1));
+            }}
+        }}
+        deserializer.decrease_container_depth();
+        return node;
+    }}
+
+    public static void main(String[] args) throws java.lang.Exception {{
+        // A sequence length prefix above `maxContainerLength` is rejected.
+        {{
+            BincodeSerializer s = new BincodeSerializer();
+            s.serialize_len(1000);
+            byte[] bytes = s.get_bytes();
+
+            BincodeDeserializer d = new BincodeDeserializer(
+                    bytes, IntEncoding.FIXED, ByteOrder.LITTLE_ENDIAN, 10, Long.MAX_VALUE);
+            boolean threw = false;
+            try {{
+                d.deserialize_len();
+            }} catch (DeserializationError e) {{
+                threw = true;
+            }}
+            assert threw;
+        }}
+
+        // Five levels of nesting: [[[[[1]]]]].
+        Node leaf = new Node();
+        leaf.isLeaf = true;
+        leaf.leafValue = 1;
+        Node level4 = new Node();
+        level4.children.add(leaf);
+        Node level3 = new Node();
+        level3.children.add(level4);
+        Node level2 = new Node();
+        level2.children.add(level3);
+        Node level1 = new Node();
+        level1.children.add(level2);
+
+        BincodeSerializer s3 = new BincodeSerializer();
+        serializeNode(level1, s3);
+        byte[] encoded = s3.get_bytes();
+
+        // With enough headroom, the real recursive deserialize succeeds.
+        {{
+            BincodeDeserializer d = new BincodeDeserializer(
+                    encoded, IntEncoding.FIXED, ByteOrder.LITTLE_ENDIAN, Long.MAX_VALUE, 10);
+            Node decoded = deserializeNode(d, 4);
+            assert decoded.children.get(0).children.get(0).children.get(0).children.get(0)
+                            .leafValue
+                    == 1;
+            assert d.remaining() == 0;
+        }}
+
+        // With a depth limit too shallow for this nesting, the same
+        // deserialize is rejected partway through -- not via a bare
+        // counter increment, but by actually recursing into the
+        // oversized structure.
+        {{
+            BincodeDeserializer d = new BincodeDeserializer(
+                    encoded, IntEncoding.FIXED, ByteOrder.LITTLE_ENDIAN, Long.MAX_VALUE, 3);
+            boolean threw = false;
+            try {{
+                deserializeNode(d, 4);
+            }} catch (DeserializationError e) {{
+                threw = true;
+            }}
+            assert threw;
+        }}
+    }}
+}}
+"#
+    )
+    .unwrap();
+
+    let paths = std::iter::empty()
+        .chain(std::fs::read_dir("runtime/java/com/facebook/serde").unwrap())
+        .chain(std::fs::read_dir("runtime/java/com/facebook/bincode").unwrap())
+        .map(|e| e.unwrap().path());
+    let status = Command::new("javac")
+        .arg("-Xlint")
+        .arg("-d")
+        .arg(dir.path())
+        .args(paths)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new("javac")
+        .arg("-Xlint")
+        .arg("-cp")
+        .arg(dir.path())
+        .arg("-d")
+        .arg(dir.path())
+        .arg(dir.path().join("Main.java"))
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let status = Command::new("java")
+        .arg("-enableassertions")
+        .arg("-cp")
+        .arg(dir.path())
+        .arg("Main")
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
@@ -0,0 +1,85 @@
+// Copyright (c) Facebook, Inc. and its affiliates
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+// Note for reviewers: as with bincode_runtime.rs and cbor_runtime.rs,
+// this checkout has no Cargo.toml/lib.rs, so this file has never been
+// compiled or run via `cargo test` here.
+use serde::{Deserialize, Serialize};
+use serde_generate::fingerprint::registry_fingerprint;
+use serde_reflection::{Registry, Result, Samples, Tracer, TracerConfig};
+
+#[derive(Serialize, Deserialize)]
+struct Test {
+    a: Vec<u32>,
+    b: (i64, u64),
+    c: Choice,
+}
+
+#[derive(Serialize, Deserialize)]
+enum Choice {
+    A,
+    B(u64),
+    C { x: u8 },
+}
+
+// Same shape as `Choice` above but with an extra variant, to check that
+// the fingerprint is sensitive to schema changes. Renamed to `Choice` for
+// serde, so that tracing it replaces the `Choice` container in the
+// registry rather than adding a second one.
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Choice")]
+enum ChoiceWithExtraVariant {
+    A,
+    B(u64),
+    C { x: u8 },
+    D(String),
+}
+
+fn get_registry() -> Result<Registry> {
+    let mut tracer = Tracer::new(TracerConfig::default());
+    let samples = Samples::new();
+    tracer.trace_type::<Test>(&samples)?;
+    tracer.trace_type::<Choice>(&samples)?;
+    Ok(tracer.registry()?)
+}
+
+#[test]
+fn test_registry_fingerprint_is_stable() {
+    let registry = get_registry().unwrap();
+    assert_eq!(registry_fingerprint(&registry), registry_fingerprint(&registry));
+}
+
+#[test]
+fn test_registry_fingerprint_is_independent_of_trace_order() {
+    let registry1 = {
+        let mut tracer = Tracer::new(TracerConfig::default());
+        let samples = Samples::new();
+        tracer.trace_type::<Test>(&samples).unwrap();
+        tracer.trace_type::<Choice>(&samples).unwrap();
+        tracer.registry().unwrap()
+    };
+    let registry2 = {
+        let mut tracer = Tracer::new(TracerConfig::default());
+        let samples = Samples::new();
+        tracer.trace_type::<Choice>(&samples).unwrap();
+        tracer.trace_type::<Test>(&samples).unwrap();
+        tracer.registry().unwrap()
+    };
+    assert_eq!(registry_fingerprint(&registry1), registry_fingerprint(&registry2));
+}
+
+#[test]
+fn test_registry_fingerprint_changes_with_schema() {
+    let registry = get_registry().unwrap();
+
+    let mut tracer = Tracer::new(TracerConfig::default());
+    let samples = Samples::new();
+    tracer.trace_type::<Test>(&samples).unwrap();
+    tracer.trace_type::<ChoiceWithExtraVariant>(&samples).unwrap();
+    let modified_registry = tracer.registry().unwrap();
+
+    assert_ne!(
+        registry_fingerprint(&registry),
+        registry_fingerprint(&modified_registry)
+    );
+}